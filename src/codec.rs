@@ -0,0 +1,103 @@
+use ntex::http;
+use ntex::web;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::HttpError;
+
+/// Wire format negotiated from a request's `Content-Type`/`Accept` header
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Codec {
+  Json,
+  Cbor,
+  MsgPack,
+}
+
+impl Codec {
+  /// Pick a codec from a raw header value, defaulting to JSON
+  fn from_header(value: Option<&str>) -> Self {
+    match value {
+      Some(value) if value.contains("application/cbor") => Codec::Cbor,
+      Some(value) if value.contains("application/msgpack") => Codec::MsgPack,
+      _ => Codec::Json,
+    }
+  }
+
+  /// Codec to use to decode a request body, based on its `Content-Type` header
+  pub fn of_request(req: &web::HttpRequest) -> Self {
+    let value = req
+      .headers()
+      .get(http::header::CONTENT_TYPE)
+      .and_then(|value| value.to_str().ok());
+    Codec::from_header(value)
+  }
+
+  /// Codec to use to encode a response body, based on the request's `Accept` header
+  pub fn of_response(req: &web::HttpRequest) -> Self {
+    let value = req
+      .headers()
+      .get(http::header::ACCEPT)
+      .and_then(|value| value.to_str().ok());
+    Codec::from_header(value)
+  }
+
+  fn content_type(&self) -> &'static str {
+    match self {
+      Codec::Json => "application/json",
+      Codec::Cbor => "application/cbor",
+      Codec::MsgPack => "application/msgpack",
+    }
+  }
+}
+
+/// Decode a request body with the given codec
+pub fn decode<T: DeserializeOwned>(codec: Codec, body: &[u8]) -> Result<T, HttpError> {
+  let decode_err = |err: std::fmt::Arguments| HttpError {
+    status: http::StatusCode::BAD_REQUEST,
+    msg: format!("Error decoding request body: {}", err),
+  };
+  match codec {
+    Codec::Json => {
+      serde_json::from_slice(body).map_err(|err| decode_err(format_args!("{}", err)))
+    }
+    Codec::Cbor => {
+      serde_cbor::from_slice(body).map_err(|err| decode_err(format_args!("{}", err)))
+    }
+    Codec::MsgPack => {
+      rmp_serde::from_slice(body).map_err(|err| decode_err(format_args!("{}", err)))
+    }
+  }
+}
+
+/// Encode a value with the given codec into an `HttpResponse` with the given status
+pub fn encode<T: Serialize>(
+  status: http::StatusCode,
+  codec: Codec,
+  value: &T,
+) -> Result<web::HttpResponse, HttpError> {
+  let encode_err = |err: std::fmt::Arguments| HttpError {
+    status: http::StatusCode::INTERNAL_SERVER_ERROR,
+    msg: format!("Error encoding response body: {}", err),
+  };
+  match codec {
+    Codec::Json => Ok(web::HttpResponse::build(status).json(value)),
+    Codec::Cbor => {
+      let body = serde_cbor::to_vec(value)
+        .map_err(|err| encode_err(format_args!("{}", err)))?;
+      Ok(
+        web::HttpResponse::build(status)
+          .content_type(codec.content_type())
+          .body(body),
+      )
+    }
+    Codec::MsgPack => {
+      let body = rmp_serde::to_vec(value)
+        .map_err(|err| encode_err(format_args!("{}", err)))?;
+      Ok(
+        web::HttpResponse::build(status)
+          .content_type(codec.content_type())
+          .body(body),
+      )
+    }
+  }
+}
@@ -1,17 +1,24 @@
 use ntex::web;
 
+mod codec;
 mod error;
+mod middleware;
 mod models;
 mod services;
 
 #[ntex::main]
 async fn main() -> std::io::Result<()> {
-  web::server(|| {
+  // Built once and shared across every worker, instead of per-worker in the app factory
+  let store = services::todo::new_store();
+  web::server(move || {
     web::App::new()
       // Register swagger endpoints
       .configure(services::openapi::ntex_config)
       // Register todo endpoints
-      .configure(services::todo::ntex_config)
+      .configure({
+        let store = store.clone();
+        move |cfg| services::todo::ntex_config(cfg, store)
+      })
       // Default endpoint for unregisterd endpoints
       .default_service(web::route().to(services::default))
   })
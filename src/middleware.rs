@@ -0,0 +1,49 @@
+use ntex::http;
+use ntex::service::{Middleware, Service, ServiceCtx};
+use ntex::web::{Error, ErrorRenderer, WebRequest, WebResponse};
+
+use crate::error::HttpError;
+
+/// Name of the header clients must send to authenticate against the todo API
+pub const API_KEY_HEADER: &str = "todo_apikey";
+
+/// Rejects requests missing the `todo_apikey` header
+pub struct ApiKeyAuth;
+
+impl<S> Middleware<S> for ApiKeyAuth {
+  type Service = ApiKeyAuthMiddleware<S>;
+
+  fn create(&self, service: S) -> Self::Service {
+    ApiKeyAuthMiddleware { service }
+  }
+}
+
+pub struct ApiKeyAuthMiddleware<S> {
+  service: S,
+}
+
+impl<S, Err> Service<WebRequest<Err>> for ApiKeyAuthMiddleware<S>
+where
+  S: Service<WebRequest<Err>, Response = WebResponse, Error = Error>,
+  Err: ErrorRenderer,
+{
+  type Response = WebResponse;
+  type Error = Error;
+
+  ntex::forward_poll_ready!(service);
+
+  async fn call(
+    &self,
+    req: WebRequest<Err>,
+    ctx: ServiceCtx<'_, Self>,
+  ) -> Result<Self::Response, Self::Error> {
+    if req.headers().get(API_KEY_HEADER).is_none() {
+      let err = HttpError {
+        status: http::StatusCode::UNAUTHORIZED,
+        msg: format!("Missing {} header", API_KEY_HEADER),
+      };
+      return Ok(req.error_response(err));
+    }
+    ctx.call(&self.service, req).await
+  }
+}
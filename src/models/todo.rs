@@ -18,3 +18,12 @@ pub struct TodoPartial {
   /// The todo title
   pub title: String,
 }
+
+/// Query options to paginate a list of Todo
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ListOptions {
+  /// Number of todos to skip
+  pub offset: Option<usize>,
+  /// Maximum number of todos to return
+  pub limit: Option<usize>,
+}
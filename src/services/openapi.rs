@@ -3,24 +3,45 @@ use std::sync::Arc;
 use ntex::web;
 use ntex::http;
 use ntex::util::Bytes;
-use utoipa::OpenApi;
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, SecurityScheme};
+use utoipa::{Modify, OpenApi};
 
 use crate::error::HttpError;
-use crate::models::todo::{Todo, TodoPartial};
+use crate::middleware::API_KEY_HEADER;
+use crate::models::todo::{ListOptions, Todo, TodoPartial};
 
 use super::todo;
 
+/// Registers the `todo_apikey` API key security scheme in the OpenAPI spec
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+  fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+    if let Some(components) = openapi.components.as_mut() {
+      components.add_security_scheme(
+        "todo_apikey",
+        SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new(
+          API_KEY_HEADER,
+        ))),
+      );
+    }
+  }
+}
+
 /// Main structure to generate OpenAPI documentation
 #[derive(OpenApi)]
 #[openapi(
   paths(
     todo::get_todos,
     todo::create_todo,
+    todo::search_todos,
     todo::get_todo,
     todo::update_todo,
     todo::delete_todo,
+    todo::mark_todo_done,
   ),
-  components(schemas(Todo, TodoPartial, HttpError))
+  components(schemas(Todo, TodoPartial, ListOptions, HttpError)),
+  modifiers(&SecurityAddon)
 )]
 pub(crate) struct ApiDoc;
 
@@ -60,6 +81,63 @@ async fn get_swagger(
   }
 }
 
+/// Path to the OpenAPI spec served by [`get_swagger`], reused by the other viewers
+const SPEC_PATH: &str = "/explorer/swagger.json";
+
+/// Renders the spec through Redoc
+#[web::get("/redoc")]
+async fn get_redoc() -> web::HttpResponse {
+  web::HttpResponse::Ok().content_type("text/html").body(format!(
+    r#"<!DOCTYPE html>
+<html>
+  <head>
+    <title>Redoc</title>
+    <meta charset="utf-8"/>
+  </head>
+  <body>
+    <redoc spec-url="{SPEC_PATH}"></redoc>
+    <script src="https://cdn.redoc.ly/redoc/latest/bundles/redoc.standalone.js"></script>
+  </body>
+</html>"#
+  ))
+}
+
+/// Renders the spec through RapiDoc
+#[web::get("/rapidoc")]
+async fn get_rapidoc() -> web::HttpResponse {
+  web::HttpResponse::Ok().content_type("text/html").body(format!(
+    r#"<!DOCTYPE html>
+<html>
+  <head>
+    <title>RapiDoc</title>
+    <meta charset="utf-8"/>
+    <script type="module" src="https://unpkg.com/rapidoc/dist/rapidoc-min.js"></script>
+  </head>
+  <body>
+    <rapi-doc spec-url="{SPEC_PATH}"></rapi-doc>
+  </body>
+</html>"#
+  ))
+}
+
+/// Renders the spec through Scalar
+#[web::get("/scalar")]
+async fn get_scalar() -> web::HttpResponse {
+  web::HttpResponse::Ok().content_type("text/html").body(format!(
+    r#"<!DOCTYPE html>
+<html>
+  <head>
+    <title>Scalar</title>
+    <meta charset="utf-8"/>
+  </head>
+  <body>
+    <script id="api-reference" data-url="{SPEC_PATH}"></script>
+    <script src="https://cdn.jsdelivr.net/npm/@scalar/api-reference"></script>
+  </body>
+</html>"#
+  ))
+}
+
 pub fn ntex_config(config: &mut web::ServiceConfig) {
   let swagger_config = Arc::new(
     utoipa_swagger_ui::Config::new(["/explorer/swagger.json"])
@@ -70,4 +148,7 @@ pub fn ntex_config(config: &mut web::ServiceConfig) {
       .state(swagger_config)
       .service(get_swagger),
   );
+  config.service(get_redoc);
+  config.service(get_rapidoc);
+  config.service(get_scalar);
 }
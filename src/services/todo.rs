@@ -1,18 +1,62 @@
+use std::sync::{Arc, Mutex};
+
 use ntex::web;
+use ntex::http;
+
+use serde::Deserialize;
 
-use crate::models::todo::TodoPartial;
+use crate::codec::{self, Codec};
+use crate::error::HttpError;
+use crate::middleware::ApiKeyAuth;
+use crate::models::todo::{ListOptions, Todo, TodoPartial};
+
+/// Query options to search todos by title
+#[derive(Clone, Debug, Deserialize)]
+pub struct SearchOptions {
+  /// Substring to match against each todo title
+  pub text: String,
+}
+
+/// Shared in-memory store holding every todo
+pub type TodoStore = Arc<Mutex<Vec<Todo>>>;
+
+/// Build an empty todo store
+pub fn new_store() -> TodoStore {
+  Arc::new(Mutex::new(Vec::new()))
+}
+
+/// Compute the next available todo id
+fn next_id(todos: &[Todo]) -> i32 {
+  todos.iter().map(|todo| todo.id).max().unwrap_or(0) + 1
+}
 
 /// List all todos
 #[utoipa::path(
   get,
   path = "/todos",
+  params(
+    ("offset" = Option<usize>, Query, description = "Number of todos to skip"),
+    ("limit" = Option<usize>, Query, description = "Maximum number of todos to return"),
+  ),
   responses(
     (status = 200, description = "List of Todo", body = [Todo]),
   ),
+  security(("todo_apikey" = [])),
 )]
 #[web::get("/todos")]
-pub async fn get_todos() -> web::HttpResponse {
-  web::HttpResponse::Ok().finish()
+pub async fn get_todos(
+  req: web::HttpRequest,
+  store: web::types::State<TodoStore>,
+  options: web::types::Query<ListOptions>,
+) -> Result<web::HttpResponse, HttpError> {
+  let todos = store.lock().unwrap();
+  let page: Vec<Todo> = todos
+    .iter()
+    .cloned()
+    .skip(options.offset.unwrap_or(0))
+    .take(options.limit.unwrap_or(todos.len()))
+    .collect();
+  codec::encode(http::StatusCode::OK, Codec::of_response(&req), &page)
 }
 
 /// Create a new todo
@@ -23,12 +67,50 @@ pub async fn get_todos() -> web::HttpResponse {
   responses(
     (status = 201, description = "Todo created", body = Todo),
   ),
+  security(("todo_apikey" = [])),
 )]
 #[web::post("/todos")]
 pub async fn create_todo(
-  _todo: web::types::Json<TodoPartial>,
-) -> web::HttpResponse {
-  web::HttpResponse::Created().finish()
+  req: web::HttpRequest,
+  store: web::types::State<TodoStore>,
+  body: web::types::Bytes,
+) -> Result<web::HttpResponse, HttpError> {
+  let payload: TodoPartial = codec::decode(Codec::of_request(&req), &body)?;
+  let mut todos = store.lock().unwrap();
+  let todo = Todo {
+    id: next_id(&todos),
+    title: payload.title,
+    completed: false,
+  };
+  todos.push(todo.clone());
+  codec::encode(http::StatusCode::CREATED, Codec::of_response(&req), &todo)
+}
+
+/// Search todos whose title contains a substring
+#[utoipa::path(
+  get,
+  path = "/todos/search",
+  params(
+    ("text" = String, Query, description = "Substring to match against each todo title"),
+  ),
+  responses(
+    (status = 200, description = "List of matching Todo", body = [Todo]),
+  ),
+  security(("todo_apikey" = [])),
+)]
+#[web::get("/todos/search")]
+pub async fn search_todos(
+  req: web::HttpRequest,
+  store: web::types::State<TodoStore>,
+  options: web::types::Query<SearchOptions>,
+) -> Result<web::HttpResponse, HttpError> {
+  let todos = store.lock().unwrap();
+  let matches: Vec<Todo> = todos
+    .iter()
+    .filter(|todo| todo.title.contains(&options.text))
+    .cloned()
+    .collect();
+  codec::encode(http::StatusCode::OK, Codec::of_response(&req), &matches)
 }
 
 /// Get a todo by id
@@ -39,10 +121,23 @@ pub async fn create_todo(
     (status = 200, description = "Todo found", body = Todo),
     (status = 404, description = "Todo not found", body = HttpError),
   ),
+  security(("todo_apikey" = [])),
 )]
 #[web::get("/todos/{id}")]
-pub async fn get_todo() -> web::HttpResponse {
-  web::HttpResponse::Ok().finish()
+pub async fn get_todo(
+  req: web::HttpRequest,
+  store: web::types::State<TodoStore>,
+  id: web::types::Path<i32>,
+) -> Result<web::HttpResponse, HttpError> {
+  let todos = store.lock().unwrap();
+  let todo = todos
+    .iter()
+    .find(|todo| todo.id == *id)
+    .ok_or_else(|| HttpError {
+      status: http::StatusCode::NOT_FOUND,
+      msg: format!("Todo with id {} not found", *id),
+    })?;
+  codec::encode(http::StatusCode::OK, Codec::of_response(&req), todo)
 }
 
 /// Update a todo by id
@@ -54,10 +149,26 @@ pub async fn get_todo() -> web::HttpResponse {
     (status = 200, description = "Todo updated", body = Todo),
     (status = 404, description = "Todo not found", body = HttpError),
   ),
+  security(("todo_apikey" = [])),
 )]
 #[web::put("/todos/{id}")]
-pub async fn update_todo() -> web::HttpResponse {
-  web::HttpResponse::Ok().finish()
+pub async fn update_todo(
+  req: web::HttpRequest,
+  store: web::types::State<TodoStore>,
+  id: web::types::Path<i32>,
+  body: web::types::Bytes,
+) -> Result<web::HttpResponse, HttpError> {
+  let payload: TodoPartial = codec::decode(Codec::of_request(&req), &body)?;
+  let mut todos = store.lock().unwrap();
+  let todo = todos
+    .iter_mut()
+    .find(|todo| todo.id == *id)
+    .ok_or_else(|| HttpError {
+      status: http::StatusCode::NOT_FOUND,
+      msg: format!("Todo with id {} not found", *id),
+    })?;
+  todo.title = payload.title;
+  codec::encode(http::StatusCode::OK, Codec::of_response(&req), todo)
 }
 
 /// Delete a todo by id
@@ -68,16 +179,65 @@ pub async fn update_todo() -> web::HttpResponse {
     (status = 200, description = "Todo deleted", body = Todo),
     (status = 404, description = "Todo not found", body = HttpError),
   ),
+  security(("todo_apikey" = [])),
 )]
 #[web::delete("/todos/{id}")]
-pub async fn delete_todo() -> web::HttpResponse {
-  web::HttpResponse::Ok().finish()
+pub async fn delete_todo(
+  req: web::HttpRequest,
+  store: web::types::State<TodoStore>,
+  id: web::types::Path<i32>,
+) -> Result<web::HttpResponse, HttpError> {
+  let mut todos = store.lock().unwrap();
+  let index = todos
+    .iter()
+    .position(|todo| todo.id == *id)
+    .ok_or_else(|| HttpError {
+      status: http::StatusCode::NOT_FOUND,
+      msg: format!("Todo with id {} not found", *id),
+    })?;
+  let todo = todos.remove(index);
+  codec::encode(http::StatusCode::OK, Codec::of_response(&req), &todo)
+}
+
+/// Mark a todo as done
+#[utoipa::path(
+  patch,
+  path = "/todos/{id}/done",
+  responses(
+    (status = 200, description = "Todo marked as done", body = Todo),
+    (status = 404, description = "Todo not found", body = HttpError),
+  ),
+  security(("todo_apikey" = [])),
+)]
+#[web::patch("/todos/{id}/done")]
+pub async fn mark_todo_done(
+  req: web::HttpRequest,
+  store: web::types::State<TodoStore>,
+  id: web::types::Path<i32>,
+) -> Result<web::HttpResponse, HttpError> {
+  let mut todos = store.lock().unwrap();
+  let todo = todos
+    .iter_mut()
+    .find(|todo| todo.id == *id)
+    .ok_or_else(|| HttpError {
+      status: http::StatusCode::NOT_FOUND,
+      msg: format!("Todo with id {} not found", *id),
+    })?;
+  todo.completed = true;
+  codec::encode(http::StatusCode::OK, Codec::of_response(&req), todo)
 }
 
-pub fn ntex_config(cfg: &mut web::ServiceConfig) {
-  cfg.service(get_todos);
-  cfg.service(create_todo);
-  cfg.service(get_todo);
-  cfg.service(update_todo);
-  cfg.service(delete_todo);
+pub fn ntex_config(cfg: &mut web::ServiceConfig, store: TodoStore) {
+  cfg.service(
+    web::scope("")
+      .state(store)
+      .wrap(ApiKeyAuth)
+      .service(get_todos)
+      .service(create_todo)
+      .service(search_todos)
+      .service(get_todo)
+      .service(update_todo)
+      .service(delete_todo)
+      .service(mark_todo_done),
+  );
 }